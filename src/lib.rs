@@ -93,8 +93,27 @@
 //! ```
 //!
 //! This is useful when you need a singleton instance of some trait, but the implementation can vary.
-use std::marker::PhantomData;
-use std::sync::atomic::*;
+//!
+//! # Embedded and `no_std`
+//!
+//! The crate is `#![no_std]`. [`StaticSlot`] still heap-allocates through `alloc`, so it needs a global allocator. For
+//! allocator-less targets there is [`InlineStaticSlot`], which stores its value inline and never touches the heap.
+//!
+//! Atomics are routed through [`portable-atomic`](https://docs.rs/portable-atomic). The crate still needs native
+//! pointer-sized atomics to build: [`SharedStaticSlot`] is always compiled in and uses `alloc::sync::Arc`, which `alloc`
+//! only provides on targets that have them.
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+extern crate portable_atomic;
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr;
+use portable_atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
 
 
 /// A container for a statically owned value.
@@ -122,9 +141,9 @@ impl<T: 'static> Default for StaticSlot<T> {
 
 impl<T: 'static> StaticSlot<T> {
     /// A static slot with its value set to `NULL`. Useful for static initialization.
+    #[allow(clippy::declare_interior_mutable_const)]
     pub const NULL: Self = Self {
-        #[doc(hidden)]
-        address: ATOMIC_USIZE_INIT,
+        address: AtomicUsize::new(0),
         _phantom: PhantomData,
     };
 
@@ -149,6 +168,7 @@ impl<T: 'static> StaticSlot<T> {
     /// This method does not perform any initialization. For optimal performance, this performs a fast check if the
     /// slot is `NULL` and, if not, returns a reference.
     #[inline]
+    #[allow(clippy::mut_from_ref)]
     pub fn get(&self) -> Option<&mut T> {
         let ptr = self.as_mut_ptr();
 
@@ -166,7 +186,12 @@ impl<T: 'static> StaticSlot<T> {
     /// If doing a null check every time you call `get()` is unnacceptable, then this unsafe variant will let you bypass
     /// that. Note that if the slot has not been initialized, the returned reference will be invalid and improper use
     /// could cause a segmentation fault.
+    ///
+    /// # Safety
+    ///
+    /// The slot must hold a value; calling this on a `NULL` slot produces a dangling reference.
     #[inline]
+    #[allow(clippy::mut_from_ref)]
     pub unsafe fn get_unchecked(&self) -> &mut T {
         &mut *self.as_mut_ptr()
     }
@@ -191,10 +216,106 @@ impl<T: 'static> StaticSlot<T> {
     ///
     /// This method is marked as unsafe because it can introduce memory leaks if `drop()` or `take()` is not manually
     /// called before the process exits.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure the stored value is eventually reclaimed with `drop()` or `take()`; otherwise its
+    /// destructor never runs.
     pub unsafe fn set(&self, value: T) {
         self.swap(Some(value));
     }
 
+    /// Gets a reference to the value in the slot, initializing it with `f` if the slot is `NULL`.
+    ///
+    /// Only one value is ever installed. If several threads race to initialize an empty slot, each of them may run its
+    /// initializer; the losers drop their freshly produced value and observe the winner's. Because the value lives for the rest
+    /// of the process once set, the returned reference is safely `'static` and no manual `drop()` is required.
+    pub fn get_or_init<F: FnOnce() -> T>(&'static self, f: F) -> &'static T {
+        match self.try_get_or_init::<_, ()>(|| Ok(f())) {
+            Ok(value) => value,
+            Err(()) => unreachable!(),
+        }
+    }
+
+    /// Gets a reference to the value in the slot, initializing it with `f` if the slot is `NULL`.
+    ///
+    /// This behaves like `get_or_init()`, but the initializer may fail. If it returns `Err`, the slot is left `NULL`
+    /// and the error is returned. As with `get_or_init()`, only one successful result is ever installed.
+    pub fn try_get_or_init<F: FnOnce() -> Result<T, E>, E>(&'static self, f: F) -> Result<&'static T, E> {
+        // Fast path: if the slot is already populated, hand back the existing value.
+        let address = self.address.load(Ordering::SeqCst);
+        if address != 0 {
+            return Ok(unsafe { &*(address as *const T) });
+        }
+
+        // Produce a value and put it on the heap so we have a stable address to publish.
+        let new_ptr = Box::into_raw(Box::new(f()?));
+
+        // Attempt to install our value, but only if the slot is still `NULL`.
+        match self.address.compare_exchange(0, new_ptr as usize, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => Ok(unsafe { &*new_ptr }),
+            Err(winner) => {
+                // Another thread won the race; free the value we allocated and use theirs.
+                drop(unsafe { Box::from_raw(new_ptr) });
+                Ok(unsafe { &*(winner as *const T) })
+            }
+        }
+    }
+
+    /// Sets the slot to a value, but only if it is currently `NULL`.
+    ///
+    /// If the slot is already populated, the slot is left untouched and the rejected value is handed back to the caller
+    /// as `Err`. This is the conditional counterpart to `set()` and, like it, forgoes automatic destruction of the
+    /// stored value: once installed, the value lives until you reclaim it with `take()`/`drop()`, and if the process
+    /// exits before then its destructor is never run. Callers are responsible for that cleanup, just as with `set()`.
+    pub fn set_if_null(&self, value: T) -> Result<(), T> {
+        match self.compare_exchange(ptr::null(), Some(value)) {
+            Ok(_) => Ok(()),
+            // Installing against `NULL` only fails because the slot was occupied, so a value is always returned.
+            Err(rejected) => Err(rejected.expect("set_if_null always supplies a value")),
+        }
+    }
+
+    /// Atomically replaces the contents of the slot only if its current pointer matches `expected`.
+    ///
+    /// This is the lowest-level mutation primitive: pass the pointer you expect the slot to currently hold (from
+    /// `as_ptr()`, or null for an empty slot) and the value to install (or `None` to clear it). On success the previous
+    /// contents are returned; on failure the slot is left untouched and the value you supplied is returned so nothing
+    /// leaks.
+    ///
+    /// Like `set()`, a value installed this way is not dropped automatically: if you never pull it back out (here, or
+    /// via `take()`/`drop()`) its destructor will not run before the process exits. Cleanup is the caller's
+    /// responsibility.
+    pub fn compare_exchange(&self, expected: *const T, value: Option<T>) -> Result<Option<T>, Option<T>> {
+        // Box the new value first so we have a stable address to publish. Keep the raw pointer so we can reclaim it if
+        // the exchange fails.
+        let (new_address, boxed) = match value {
+            Some(v) => {
+                let ptr = Box::into_raw(Box::new(v));
+                (ptr as usize, ptr)
+            }
+            None => (0, ptr::null_mut()),
+        };
+
+        match self.address.compare_exchange(expected as usize, new_address, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(old_address) => {
+                if old_address != 0 {
+                    Ok(Some(unsafe { *Box::from_raw(old_address as *mut T) }))
+                } else {
+                    Ok(None)
+                }
+            }
+            Err(_) => {
+                // The slot did not match `expected`; reclaim the box we allocated and return its value unchanged.
+                if !boxed.is_null() {
+                    Err(Some(unsafe { *Box::from_raw(boxed) }))
+                } else {
+                    Err(None)
+                }
+            }
+        }
+    }
+
     /// Invokes a closure, with the slot set to a given value.
     ///
     /// This method introduces a safe, controlled lifetime for the contained value. The value is shared for the duration
@@ -230,6 +351,32 @@ impl<T: 'static> StaticSlot<T> {
         }
     }
 
+    /// Takes the value out of the slot and returns it as a `'static` mutable reference that will never be dropped.
+    ///
+    /// The slot is cleared to `NULL` and made available for reuse, but the contained value's destructor will never run
+    /// — this is the equivalent of `Box::leak` for the stored value. Like `set()`, this intentionally forgoes
+    /// destruction; it is meant for values that were only ever intended to live for the duration of the process.
+    ///
+    /// Returns `None` if the slot was empty.
+    pub fn leak(&self) -> Option<&'static mut T> {
+        let old_address = self.address.swap(0, Ordering::SeqCst);
+
+        if old_address != 0 {
+            Some(unsafe { &mut *(old_address as *mut T) })
+        } else {
+            None
+        }
+    }
+
+    /// Clears the slot to `NULL` without dropping or returning the contained value.
+    ///
+    /// This is useful when ownership of the value has been transferred elsewhere (for example via a raw pointer taken
+    /// from `as_ptr()`) and the slot should simply relinquish it. No memory is freed here, so the value must be owned
+    /// by something else to avoid a leak.
+    pub fn forget(&self) {
+        self.address.store(0, Ordering::SeqCst);
+    }
+
     /// Set the current value, returning the old value.
     unsafe fn swap(&self, value: Option<T>) -> Option<T> {
         // If a value is given, put it on the heap and get its address. Otherwise use null.
@@ -254,9 +401,233 @@ unsafe impl<T: Send> Send for StaticSlot<T> {}
 unsafe impl<T: Sync> Sync for StaticSlot<T> {}
 
 
+/// Slot state: no value stored.
+const EMPTY: u8 = 0;
+/// Slot state: a value is being written or taken; storage must not be read.
+const BUSY: u8 = 1;
+/// Slot state: a value is stored and may be read.
+const FULL: u8 = 2;
+
+/// A heap-free container for a statically owned value.
+///
+/// This is a sibling of [`StaticSlot`] that stores its value directly inside the static instead of on the heap. It is
+/// useful on allocator-less embedded targets and avoids the pointer chase on every access, at the cost of making the
+/// static as large as the value it holds.
+///
+/// The API mirrors [`StaticSlot`]: the slot starts out empty, can be populated with `set()`, read with `get()`, and
+/// emptied with `take()` or `drop()`. Like `StaticSlot`, destructors are not run automatically; you *must* clean up your
+/// resources manually.
+pub struct InlineStaticSlot<T> {
+    /// Inline storage for the value.
+    storage: UnsafeCell<MaybeUninit<T>>,
+    /// Tracks whether `storage` holds an initialized value.
+    state: AtomicU8,
+}
+
+impl<T: 'static> Default for InlineStaticSlot<T> {
+    /// Create a new inline slot that is empty.
+    fn default() -> Self {
+        Self::new_uninit()
+    }
+}
+
+impl<T: 'static> InlineStaticSlot<T> {
+    /// An empty inline slot. Useful for static initialization.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const NULL: Self = Self::new_uninit();
+
+    /// Create a new, empty inline slot.
+    pub const fn new_uninit() -> Self {
+        Self {
+            storage: UnsafeCell::new(MaybeUninit::uninit()),
+            state: AtomicU8::new(EMPTY),
+        }
+    }
+
+    /// Check if the slot is empty.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.state.load(Ordering::SeqCst) != FULL
+    }
+
+    /// Gets a reference to the value in the slot, if set.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn get(&self) -> Option<&mut T> {
+        if self.state.load(Ordering::SeqCst) == FULL {
+            unsafe {
+                Some(&mut *(*self.storage.get()).as_mut_ptr())
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Sets the inline slot to a new value. If the slot was already set, the old value is dropped.
+    ///
+    /// This method is marked as unsafe because it can introduce leaks if `drop()` or `take()` is not manually called
+    /// before the process exits. It also assumes no other thread is concurrently accessing the same slot.
+    ///
+    /// # Safety
+    ///
+    /// No other thread may access the slot concurrently, and the caller must ensure the stored value is eventually
+    /// reclaimed with `drop()` or `take()`; otherwise its destructor never runs.
+    pub unsafe fn set(&self, value: T) {
+        self.take();
+        self.state.store(BUSY, Ordering::SeqCst);
+        ptr::write((*self.storage.get()).as_mut_ptr(), value);
+        self.state.store(FULL, Ordering::SeqCst);
+    }
+
+    /// Takes the value out of the slot if it exists.
+    pub fn take(&self) -> Option<T> {
+        if self.state.compare_exchange(FULL, BUSY, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            let value = unsafe {
+                ptr::read((*self.storage.get()).as_ptr())
+            };
+            self.state.store(EMPTY, Ordering::SeqCst);
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Drops the value in the slot if any, and returns if a value was dropped.
+    pub fn drop(&self) -> bool {
+        self.take().is_some()
+    }
+}
+
+unsafe impl<T: Send> Send for InlineStaticSlot<T> {}
+unsafe impl<T: Sync> Sync for InlineStaticSlot<T> {}
+
+
+/// A statically owned value shared through reference counting.
+///
+/// [`StaticSlot::with`] reclaims its value as soon as the closure returns, so a borrow cannot be handed to something
+/// that outlives the scope. This sibling instead stores the value as an `Arc<T>` and hands out clones with
+/// [`lease()`](Self::lease): a lease keeps the value alive through its refcount even after the slot is cleared with
+/// [`take()`](Self::take)/[`drop()`](Self::drop), so the memory is only freed once the slot is empty *and* every
+/// outstanding lease is gone.
+///
+/// The stored `Arc` lives behind its own `UnsafeCell`, so — unlike the owned [`StaticSlot`] API — there is no raw
+/// pointer word that the two can disagree about reinterpreting. Every access is guarded by a small spin lock, so
+/// leasing is sound even against a concurrent publish or clear from another thread.
+pub struct SharedStaticSlot<T> {
+    /// The shared value, if any. Only ever touched while `locked` is held.
+    value: UnsafeCell<Option<Arc<T>>>,
+    /// Spin lock guarding access to `value`.
+    locked: AtomicBool,
+}
+
+impl<T: 'static> Default for SharedStaticSlot<T> {
+    /// Create a new shared slot that is empty.
+    fn default() -> Self {
+        Self::NULL
+    }
+}
+
+impl<T: 'static> SharedStaticSlot<T> {
+    /// An empty shared slot. Useful for static initialization.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const NULL: Self = Self {
+        value: UnsafeCell::new(None),
+        locked: AtomicBool::new(false),
+    };
+
+    /// Check if the slot is empty.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.lock();
+        let empty = unsafe { &*self.value.get() }.is_none();
+        self.unlock();
+        empty
+    }
+
+    /// Stores a value in the slot, publishing it as a shared `Arc<T>`.
+    ///
+    /// If the slot already held a value it is released here; any outstanding leases keep the old value alive until their
+    /// refcounts drop to zero.
+    pub fn set_shared(&self, value: T) {
+        // Drop the previous value only after releasing the lock, so an arbitrary destructor never runs inside the
+        // critical section.
+        let _previous = self.replace(Some(Arc::new(value)));
+    }
+
+    /// Invokes a closure, with the slot set to a given shared value.
+    ///
+    /// This is the shared counterpart to [`StaticSlot::with`]: the value is published for the duration of the closure
+    /// and any previous value is restored afterwards. Because leases are reference-counted, an `Arc` obtained with
+    /// `lease()` inside the closure remains valid after the closure returns.
+    pub fn with_shared<R, F: FnOnce() -> R>(&self, value: T, f: F) -> R {
+        let previous = self.replace(Some(Arc::new(value)));
+        let result = f();
+        self.replace(previous);
+        result
+    }
+
+    /// Obtains a shared lease on the value in the slot, if any.
+    ///
+    /// The returned `Arc<T>` keeps the value alive via its refcount even after the slot is cleared, allowing the borrow
+    /// to outlive the scope it was taken from. Returns `None` if the slot is empty. The clone is taken under the slot's
+    /// lock, so it is safe even if another thread republishes or clears the slot concurrently.
+    pub fn lease(&self) -> Option<Arc<T>> {
+        self.lock();
+        let leased = unsafe { &*self.value.get() }.clone();
+        self.unlock();
+        leased
+    }
+
+    /// Takes the value out of the slot if it exists, returning the shared `Arc`.
+    ///
+    /// The slot is left empty. The returned `Arc` — and any outstanding leases — keep the value alive until their
+    /// refcounts drop to zero.
+    pub fn take(&self) -> Option<Arc<T>> {
+        self.replace(None)
+    }
+
+    /// Releases the slot's hold on the value, and returns whether a value was present.
+    ///
+    /// Any outstanding leases keep the value alive until their refcounts drop to zero.
+    pub fn drop(&self) -> bool {
+        self.take().is_some()
+    }
+
+    /// Swap in a new value under the lock, returning the previous one to be dropped by the caller.
+    fn replace(&self, value: Option<Arc<T>>) -> Option<Arc<T>> {
+        self.lock();
+        let previous = unsafe { &mut *self.value.get() }.take();
+        unsafe { *self.value.get() = value };
+        self.unlock();
+        previous
+    }
+
+    /// Acquire the spin lock guarding `value`.
+    #[inline]
+    fn lock(&self) {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Release the spin lock guarding `value`.
+    #[inline]
+    fn unlock(&self) {
+        self.locked.store(false, Ordering::Release);
+    }
+}
+
+unsafe impl<T: Send + Sync> Send for SharedStaticSlot<T> {}
+unsafe impl<T: Send + Sync> Sync for SharedStaticSlot<T> {}
+
+
 #[cfg(test)]
 mod tests {
-    use super::StaticSlot;
+    use super::{InlineStaticSlot, SharedStaticSlot, StaticSlot};
 
     #[test]
     fn test_is_small() {
@@ -269,20 +640,20 @@ mod tests {
     fn test_basic_usage() {
         static VALUE: StaticSlot<i32> = StaticSlot::NULL;
 
-        assert!(VALUE.get() == None);
+        assert!(VALUE.get().is_none());
         unsafe {
             VALUE.set(1);
         }
         assert!(VALUE.get() == Some(&mut 1));
         VALUE.drop();
-        assert!(VALUE.get() == None);
+        assert!(VALUE.get().is_none());
     }
 
     #[test]
     fn test_with() {
         static VALUE: StaticSlot<i32> = StaticSlot::NULL;
 
-        assert!(VALUE.get() == None);
+        assert!(VALUE.get().is_none());
 
         VALUE.with(1, || {
             assert!(VALUE.get() == Some(&mut 1));
@@ -294,6 +665,36 @@ mod tests {
             assert!(VALUE.get() == Some(&mut 1));
         });
 
-        assert!(VALUE.get() == None);
+        assert!(VALUE.get().is_none());
+    }
+
+    #[test]
+    fn test_inline_basic_usage() {
+        static VALUE: InlineStaticSlot<i32> = InlineStaticSlot::NULL;
+
+        assert!(VALUE.get().is_none());
+        unsafe {
+            VALUE.set(1);
+        }
+        assert!(VALUE.get() == Some(&mut 1));
+        assert!(VALUE.take() == Some(1));
+        assert!(VALUE.get().is_none());
+    }
+
+    #[test]
+    fn test_lease_outlives_scope() {
+        use alloc::sync::Arc;
+
+        static VALUE: SharedStaticSlot<i32> = SharedStaticSlot::NULL;
+
+        let leased: Arc<i32> = VALUE.with_shared(7, || {
+            let lease = VALUE.lease().unwrap();
+            assert!(*lease == 7);
+            lease
+        });
+
+        // The slot has been cleared, but the lease keeps the value alive.
+        assert!(*leased == 7);
+        assert!(VALUE.lease().is_none());
     }
 }